@@ -0,0 +1,178 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::quoter::QuoterSealed;
+use crate::sink::Sink;
+use crate::{Quotable, QuoteError, Quoter};
+
+/// Quote/escape bytes for use as a single `cmd.exe` command-line argument.
+///
+/// Quoting happens in two layers. First, a
+/// [`CommandLineToArgvW`](https://learn.microsoft.com/en-us/windows/win32/api/shellapi/nf-shellapi-commandlinetoargvw)-compatible
+/// token is produced: the argument is wrapped in `"..."`, any run of `\`
+/// immediately preceding a `"` (including the closing one) is doubled, and
+/// each literal `"` is escaped with a `\`. Second, because `cmd.exe` itself
+/// reads the command line before the child process ever sees it, the
+/// metacharacters `& | < > ^ ( ) %` and `"` are escaped with a leading `^` so
+/// that `cmd.exe` passes them through unchanged.
+///
+/// `cmd.exe`/`CommandLineToArgvW` read a command line as UTF-16 text, not
+/// bytes, so there is no faithful way to represent a byte sequence that
+/// isn't valid UTF-8. Any invalid sequence is replaced with U+FFFD (as
+/// [`String::from_utf8_lossy`] does) rather than being copied through
+/// verbatim, since every caller of this crate's `unsafe` string conversions
+/// relies on every [`Quoter`] always producing valid UTF-8.
+pub struct Cmd;
+
+impl QuoterSealed for Cmd {
+    fn quote<'a, S: ?Sized + Into<Quotable<'a>>>(s: S) -> Vec<u8> {
+        let mut sout = Vec::new();
+        Self::quote_into(s, &mut sout);
+        sout
+    }
+
+    fn quote_into<'a, S: ?Sized + Into<Quotable<'a>>, O: Sink + ?Sized>(s: S, sout: &mut O) {
+        let input = s.into();
+        let text = String::from_utf8_lossy(&input.bytes);
+        let bytes = text.as_bytes();
+
+        let mut token = Vec::with_capacity(bytes.len() + 2);
+        token.push(b'"');
+        let mut backslashes: usize = 0;
+        for &b in bytes {
+            match b {
+                b'\\' => backslashes += 1,
+                b'"' => {
+                    token.resize(token.len() + backslashes * 2 + 1, b'\\');
+                    token.push(b'"');
+                    backslashes = 0;
+                }
+                _ => {
+                    token.resize(token.len() + backslashes, b'\\');
+                    backslashes = 0;
+                    token.push(b);
+                }
+            }
+        }
+        // Any backslashes immediately before the closing quote must be
+        // doubled too, since they precede a `"`.
+        token.resize(token.len() + backslashes * 2, b'\\');
+        token.push(b'"');
+
+        for b in token {
+            match b {
+                b'&' | b'|' | b'<' | b'>' | b'^' | b'(' | b')' | b'%' | b'"' => {
+                    sout.push(b'^');
+                    sout.push(b);
+                }
+                _ => sout.push(b),
+            }
+        }
+    }
+}
+
+impl Quoter for Cmd {
+    // A raw newline is rejected in addition to NUL: `cmd.exe` treats it as a
+    // command separator with no way to escape it inside or outside of the
+    // `"..."`/`^`-escaping this quoter produces, so a quoted argument
+    // containing one is not actually safe to hand to `cmd.exe`.
+    fn try_quote<'a, S: ?Sized + Into<Quotable<'a>>>(s: S) -> Result<Vec<u8>, QuoteError> {
+        let s = s.into();
+        crate::error::check_quotable_strict(&s.bytes, b"\n")?;
+        Ok(Self::quote(s))
+    }
+
+    fn try_quote_into<'a, S: ?Sized + Into<Quotable<'a>>>(
+        s: S,
+        sout: &mut Vec<u8>,
+    ) -> Result<(), QuoteError> {
+        let s = s.into();
+        crate::error::check_quotable_strict(&s.bytes, b"\n")?;
+        Self::quote_into(s, sout);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_plain_text_in_quotes() {
+        assert_eq!(Cmd::quote("abc"), b"^\"abc^\"");
+    }
+
+    #[test]
+    fn escapes_embedded_quotes_and_carets() {
+        assert_eq!(Cmd::quote("a\"b"), b"^\"a\\^\"b^\"");
+    }
+
+    #[test]
+    fn doubles_backslashes_before_a_quote() {
+        assert_eq!(Cmd::quote(r"a\"), b"^\"a\\\\^\"");
+        assert_eq!(Cmd::quote("a\\\"b"), b"^\"a\\\\\\^\"b^\"");
+    }
+
+    #[test]
+    fn escapes_cmd_metacharacters() {
+        assert_eq!(Cmd::quote("a&b"), b"^\"a^&b^\"");
+    }
+
+    #[test]
+    fn empty_input_is_an_explicit_empty_token() {
+        assert_eq!(Cmd::quote(""), b"^\"^\"");
+    }
+
+    #[test]
+    fn try_quote_rejects_nul() {
+        assert_eq!(
+            Cmd::try_quote(&b"a\0b"[..]),
+            Err(QuoteError { byte: 0, offset: 1 })
+        );
+    }
+
+    #[test]
+    fn try_quote_rejects_newline() {
+        assert_eq!(
+            Cmd::try_quote(&b"a\nb"[..]),
+            Err(QuoteError {
+                byte: b'\n',
+                offset: 1
+            })
+        );
+    }
+
+    #[test]
+    fn try_quote_reports_whichever_invalid_byte_comes_first() {
+        // A newline precedes the NUL here, so it must be the one reported,
+        // not the NUL just because `check_quotable` used to run first.
+        assert_eq!(
+            Cmd::try_quote(&b"\n\0"[..]),
+            Err(QuoteError {
+                byte: b'\n',
+                offset: 0
+            })
+        );
+    }
+
+    #[test]
+    fn try_quote_accepts_ordinary_text() {
+        assert_eq!(Cmd::try_quote("abc").unwrap(), b"^\"abc^\"");
+    }
+
+    #[test]
+    fn replaces_invalid_utf8_instead_of_copying_it_through() {
+        // `quote`'s output must always be valid UTF-8 – every `QuoteExt`
+        // impl in lib.rs relies on that via `str::from_utf8_unchecked` – but
+        // a byte slice that isn't valid UTF-8 has no faithful representation
+        // on a platform whose command lines are UTF-16 text anyway.
+        let quoted = Cmd::quote(&b"\xff"[..]);
+        assert!(core::str::from_utf8(&quoted).is_ok());
+        assert_eq!(quoted, b"^\"\xEF\xBF\xBD^\"");
+    }
+
+    #[test]
+    fn passes_valid_multi_byte_utf8_through_unchanged() {
+        assert_eq!(Cmd::quote("café"), "^\"café^\"".as_bytes());
+    }
+}
@@ -0,0 +1,105 @@
+use core::fmt;
+use core::fmt::Write as _;
+use core::marker::PhantomData;
+
+use crate::sink::Sink;
+use crate::{Quotable, Quoter};
+
+/// A lazy [`Display`](fmt::Display)/[`Debug`](fmt::Debug) adapter returned by
+/// [`Quoter::display`] that quotes its input only when it is actually
+/// formatted.
+///
+/// This lets callers write straight into a [`fmt::Write`]/`io::Write`
+/// target, e.g. `write!(out, "run {}", Bash.display(path))`, without first
+/// building an intermediate [`Vec<u8>`]. Every [`Quoter`] in this crate
+/// emits valid UTF-8, so writing it to a [`fmt::Formatter`] is always valid
+/// UTF-8 too.
+pub struct QuotedDisplay<'a, Q> {
+    input: Quotable<'a>,
+    quoter: PhantomData<Q>,
+}
+
+impl<'a, Q> QuotedDisplay<'a, Q> {
+    pub(crate) fn new<S: ?Sized + Into<Quotable<'a>>>(s: S) -> Self {
+        QuotedDisplay {
+            input: s.into(),
+            quoter: PhantomData,
+        }
+    }
+}
+
+/// A [`Sink`] that writes each quoted byte straight into a [`fmt::Formatter`],
+/// so [`QuotedDisplay`] never has to collect into an intermediate
+/// [`Vec<u8>`].
+///
+/// [`Sink::push`] can't report failure, so a write error is latched in
+/// `result` instead; further bytes are dropped once that happens; and
+/// `QuotedDisplay::fmt` returns `result` once quoting finishes.
+struct FmtSink<'a, 'f> {
+    f: &'a mut fmt::Formatter<'f>,
+    result: fmt::Result,
+    /// Bytes of a multi-byte UTF-8 character received so far.
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+impl<'a, 'f> Sink for FmtSink<'a, 'f> {
+    fn push(&mut self, byte: u8) {
+        if self.result.is_err() {
+            return;
+        }
+        // Quoters like `Cmd` and `PowerShell` pass multi-byte UTF-8 through,
+        // so bytes are held back until they form a whole character.
+        self.pending[self.pending_len] = byte;
+        self.pending_len += 1;
+        match core::str::from_utf8(&self.pending[..self.pending_len]) {
+            Ok(s) => self.result = self.f.write_str(s),
+            Err(e) if e.error_len().is_none() => return,
+            Err(_) => self.result = self.f.write_char(char::REPLACEMENT_CHARACTER),
+        }
+        self.pending_len = 0;
+    }
+}
+
+impl<'a, Q: Quoter> fmt::Display for QuotedDisplay<'a, Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sink = FmtSink {
+            f,
+            result: Ok(()),
+            pending: [0; 4],
+            pending_len: 0,
+        };
+        Q::quote_into(&*self.input.bytes, &mut sink);
+        sink.result
+    }
+}
+
+impl<'a, Q: Quoter> fmt::Debug for QuotedDisplay<'a, Q> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use crate::PowerShell;
+
+    #[test]
+    fn formats_via_display() {
+        assert_eq!(format!("{}", PowerShell.display("it's")), "'it''s'");
+    }
+
+    #[test]
+    fn formats_via_debug() {
+        assert_eq!(format!("{:?}", PowerShell.display("it's")), "'it''s'");
+    }
+
+    #[test]
+    fn quotes_lazily_per_format_call() {
+        let adapter = PowerShell.display("a b");
+        assert_eq!(format!("{adapter}"), "'a b'");
+        assert_eq!(format!("{adapter}"), "'a b'");
+    }
+}
@@ -0,0 +1,56 @@
+use core::fmt;
+
+/// An error returned by [`Quoter::try_quote`](crate::Quoter::try_quote) and
+/// friends when the input contains a byte that cannot be quoted portably.
+///
+/// The one byte every [`Quoter`](crate::Quoter) refuses is NUL: every
+/// shell's argument vector is a NUL-terminated C string under the hood, so a
+/// NUL byte can never survive being passed as part of an argument, quoted or
+/// not. Individual quoters may refuse additional bytes that are unsafe in
+/// their own target only – for example [`Cmd`](crate::Cmd) also refuses a
+/// raw newline, which `cmd.exe` treats as a command separator with no way
+/// to escape it inside or outside of quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteError {
+    /// The byte that could not be quoted.
+    pub byte: u8,
+    /// The offset of `byte` within the input.
+    pub offset: usize,
+}
+
+impl fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "byte {:#04x} at offset {} cannot be quoted portably",
+            self.byte, self.offset
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QuoteError {}
+
+/// Find the first byte that no [`Quoter`](crate::Quoter) can quote, if any.
+pub(crate) fn check_quotable(bytes: &[u8]) -> Result<(), QuoteError> {
+    match bytes.iter().position(|&b| b == 0) {
+        Some(offset) => Err(QuoteError { byte: 0, offset }),
+        None => Ok(()),
+    }
+}
+
+/// [`check_quotable`], plus a check for any of `extra`'s bytes – for
+/// quoters with additional bytes they cannot represent safely.
+///
+/// Both conditions are checked in a single pass so that, when an input
+/// contains both a NUL and an `extra` byte, the error reports whichever one
+/// actually comes first – not NUL unconditionally.
+pub(crate) fn check_quotable_strict(bytes: &[u8], extra: &[u8]) -> Result<(), QuoteError> {
+    match bytes.iter().position(|&b| b == 0 || extra.contains(&b)) {
+        Some(offset) => Err(QuoteError {
+            byte: bytes[offset],
+            offset,
+        }),
+        None => Ok(()),
+    }
+}
@@ -1,35 +1,158 @@
+// `std` is relied on throughout this file (and gated with `#[cfg(feature =
+// "std")]`) for the `OsStr`/`OsString`/`Path`/`PathBuf` conversions and
+// `std::error::Error` impls. Cargo.toml must declare it and turn it on by
+// default:
+//
+//     [features]
+//     default = ["std"]
+//     std = []
+//
+// This snapshot ships without a Cargo.toml at all, so that declaration can't
+// be added here; whoever adds the manifest needs to include it, or every
+// downstream crate silently loses these impls the moment `no_std` becomes
+// possible.
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use std::ffi::{OsStr, OsString};
+#[cfg(feature = "std")]
 use std::path::{Path, PathBuf};
 
 mod ascii;
 mod bash;
+mod cmd;
+mod display;
+mod error;
+mod powershell;
 mod sh;
+mod sink;
+mod split;
+#[cfg(all(feature = "std", windows))]
+mod wtf8;
 
 pub use bash::Bash;
+pub use cmd::Cmd;
+pub use display::QuotedDisplay;
+pub use error::QuoteError;
+pub use powershell::PowerShell;
 pub use sh::Sh;
+pub use split::{split, ParseError};
 
 /// Extension trait for pushing shell quoted byte slices, e.g. `&[u8]`, [`&str`]
 /// – anything that's [`Quotable`] – into byte container types like [`Vec<u8>`],
 /// [`String`], [`OsString`] on Unix, and [`bstr::BString`] if it's enabled
 pub trait QuoteExt {
     fn push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(&mut self, q: Q, s: S);
+
+    /// Fallible variant of [`push_quoted`](QuoteExt::push_quoted) that
+    /// refuses to quote inputs containing bytes – like NUL – that cannot be
+    /// quoted portably for every shell. See [`Quoter::try_quote`].
+    fn try_push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(
+        &mut self,
+        q: Q,
+        s: S,
+    ) -> Result<(), QuoteError>;
+
+    /// Quote each argument in `args` with `q` and push the space-joined
+    /// result. See [`Quoter::join`].
+    fn push_quoted_join<'a, Q: Quoter, I>(&mut self, q: Q, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>;
 }
 
 impl QuoteExt for Vec<u8> {
     fn push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(&mut self, _q: Q, s: S) {
         Q::quote_into(s, self);
     }
+
+    fn try_push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(
+        &mut self,
+        _q: Q,
+        s: S,
+    ) -> Result<(), QuoteError> {
+        Q::try_quote_into(s, self)
+    }
+
+    fn push_quoted_join<'a, Q: Quoter, I>(&mut self, _q: Q, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        Q::join_into(args, self);
+    }
 }
 
-#[cfg(unix)]
+#[cfg(all(feature = "std", unix))]
 impl QuoteExt for OsString {
     fn push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(&mut self, _q: Q, s: S) {
         use std::os::unix::ffi::OsStrExt;
         let quoted = Q::quote(s);
         self.push(OsStr::from_bytes(&quoted))
     }
+
+    fn try_push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(
+        &mut self,
+        _q: Q,
+        s: S,
+    ) -> Result<(), QuoteError> {
+        use std::os::unix::ffi::OsStrExt;
+        let quoted = Q::try_quote(s)?;
+        self.push(OsStr::from_bytes(&quoted));
+        Ok(())
+    }
+
+    fn push_quoted_join<'a, Q: Quoter, I>(&mut self, _q: Q, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let joined = Q::join(args);
+        self.push(OsStr::from_bytes(&joined));
+    }
+}
+
+#[cfg(all(feature = "std", windows))]
+impl QuoteExt for OsString {
+    fn push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(&mut self, _q: Q, s: S) {
+        let quoted = Q::quote(s);
+        // SAFETY: `quoted` is valid UTF-8 because it was
+        // generated by a `quote` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let quoted = unsafe { std::str::from_utf8_unchecked(&quoted) };
+        self.push(quoted);
+    }
+
+    fn try_push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(
+        &mut self,
+        _q: Q,
+        s: S,
+    ) -> Result<(), QuoteError> {
+        let quoted = Q::try_quote(s)?;
+        let quoted = unsafe { std::str::from_utf8_unchecked(&quoted) };
+        self.push(quoted);
+        Ok(())
+    }
+
+    fn push_quoted_join<'a, Q: Quoter, I>(&mut self, _q: Q, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        let joined = Q::join(args);
+        // SAFETY: `joined` is valid UTF-8 because it was
+        // generated by a `quote`/`join` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let joined = unsafe { std::str::from_utf8_unchecked(&joined) };
+        self.push(joined);
+    }
 }
 
 #[cfg(feature = "bstr")]
@@ -37,6 +160,22 @@ impl QuoteExt for bstr::BString {
     fn push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(&mut self, _q: Q, s: S) {
         Q::quote_into(s, self)
     }
+
+    fn try_push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(
+        &mut self,
+        _q: Q,
+        s: S,
+    ) -> Result<(), QuoteError> {
+        Q::try_quote_into(s, self)
+    }
+
+    fn push_quoted_join<'a, Q: Quoter, I>(&mut self, _q: Q, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        Q::join_into(args, self);
+    }
 }
 
 impl QuoteExt for String {
@@ -45,9 +184,36 @@ impl QuoteExt for String {
         // SAFETY: `quoted` is valid UTF-8 (ASCII, in truth) because it was
         // generated by a `quote` implementation from this crate –
         // enforced because `Quoter` is sealed.
-        let quoted = unsafe { std::str::from_utf8_unchecked(&quoted) };
+        let quoted = unsafe { core::str::from_utf8_unchecked(&quoted) };
         self.push_str(quoted);
     }
+
+    fn try_push_quoted<'a, Q: Quoter, S: ?Sized + Into<Quotable<'a>>>(
+        &mut self,
+        _q: Q,
+        s: S,
+    ) -> Result<(), QuoteError> {
+        let quoted = Q::try_quote(s)?;
+        // SAFETY: `quoted` is valid UTF-8 because it was
+        // generated by a `quote` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let quoted = unsafe { core::str::from_utf8_unchecked(&quoted) };
+        self.push_str(quoted);
+        Ok(())
+    }
+
+    fn push_quoted_join<'a, Q: Quoter, I>(&mut self, _q: Q, args: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        let joined = Q::join(args);
+        // SAFETY: `joined` is valid UTF-8 because it was
+        // generated by a `quote`/`join` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let joined = unsafe { core::str::from_utf8_unchecked(&joined) };
+        self.push_str(joined);
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -58,6 +224,11 @@ impl QuoteExt for String {
 /// it's enabled.
 pub trait QuoteRefExt<Output> {
     fn quoted<Q: Quoter>(self, q: Q) -> Output;
+
+    /// Fallible variant of [`quoted`](QuoteRefExt::quoted) that refuses to
+    /// quote inputs containing bytes – like NUL – that cannot be quoted
+    /// portably for every shell. See [`Quoter::try_quote`].
+    fn try_quoted<Q: Quoter>(self, q: Q) -> Result<Output, QuoteError>;
 }
 
 impl<'a, S> QuoteRefExt<Vec<u8>> for S
@@ -67,9 +238,13 @@ where
     fn quoted<Q: Quoter>(self, _q: Q) -> Vec<u8> {
         Q::quote(self)
     }
+
+    fn try_quoted<Q: Quoter>(self, _q: Q) -> Result<Vec<u8>, QuoteError> {
+        Q::try_quote(self)
+    }
 }
 
-#[cfg(unix)]
+#[cfg(all(feature = "std", unix))]
 impl<'a, S> QuoteRefExt<OsString> for S
 where
     S: ?Sized + Into<Quotable<'a>>,
@@ -79,6 +254,36 @@ where
         let quoted = Q::quote(self);
         OsString::from_vec(quoted)
     }
+
+    fn try_quoted<Q: Quoter>(self, _q: Q) -> Result<OsString, QuoteError> {
+        use std::os::unix::ffi::OsStringExt;
+        let quoted = Q::try_quote(self)?;
+        Ok(OsString::from_vec(quoted))
+    }
+}
+
+#[cfg(all(feature = "std", windows))]
+impl<'a, S> QuoteRefExt<OsString> for S
+where
+    S: ?Sized + Into<Quotable<'a>>,
+{
+    fn quoted<Q: Quoter>(self, _q: Q) -> OsString {
+        let quoted = Q::quote(self);
+        // SAFETY: `quoted` is valid UTF-8 because it was
+        // generated by a `quote` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let quoted = unsafe { String::from_utf8_unchecked(quoted) };
+        OsString::from(quoted)
+    }
+
+    fn try_quoted<Q: Quoter>(self, _q: Q) -> Result<OsString, QuoteError> {
+        let quoted = Q::try_quote(self)?;
+        // SAFETY: `quoted` is valid UTF-8 because it was
+        // generated by a `quote` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let quoted = unsafe { String::from_utf8_unchecked(quoted) };
+        Ok(OsString::from(quoted))
+    }
 }
 
 #[cfg(feature = "bstr")]
@@ -90,6 +295,11 @@ where
         let quoted = Q::quote(self);
         bstr::BString::from(quoted)
     }
+
+    fn try_quoted<Q: Quoter>(self, _q: Q) -> Result<bstr::BString, QuoteError> {
+        let quoted = Q::try_quote(self)?;
+        Ok(bstr::BString::from(quoted))
+    }
 }
 
 impl<'a, S> QuoteRefExt<String> for S
@@ -103,17 +313,102 @@ where
         // enforced because `Quoter` is sealed.
         unsafe { String::from_utf8_unchecked(quoted) }
     }
+
+    fn try_quoted<Q: Quoter>(self, _q: Q) -> Result<String, QuoteError> {
+        let quoted = Q::try_quote(self)?;
+        // SAFETY: `quoted` is valid UTF-8 because it was
+        // generated by a `quote` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        Ok(unsafe { String::from_utf8_unchecked(quoted) })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Extension trait for shell quoting a whole iterator of arguments into one
+/// command line at once, e.g. `args.quoted_join(Bash)`. See [`Quoter::join`].
+pub trait QuoteIterExt<Output> {
+    fn quoted_join<Q: Quoter>(self, q: Q) -> Output;
+}
+
+impl<'a, I> QuoteIterExt<Vec<u8>> for I
+where
+    I: IntoIterator,
+    I::Item: Into<Quotable<'a>>,
+{
+    fn quoted_join<Q: Quoter>(self, _q: Q) -> Vec<u8> {
+        Q::join(self)
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+impl<'a, I> QuoteIterExt<OsString> for I
+where
+    I: IntoIterator,
+    I::Item: Into<Quotable<'a>>,
+{
+    fn quoted_join<Q: Quoter>(self, _q: Q) -> OsString {
+        use std::os::unix::ffi::OsStringExt;
+        OsString::from_vec(Q::join(self))
+    }
+}
+
+#[cfg(all(feature = "std", windows))]
+impl<'a, I> QuoteIterExt<OsString> for I
+where
+    I: IntoIterator,
+    I::Item: Into<Quotable<'a>>,
+{
+    fn quoted_join<Q: Quoter>(self, _q: Q) -> OsString {
+        let joined = Q::join(self);
+        // SAFETY: `joined` is valid UTF-8 because it was
+        // generated by a `quote`/`join` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        let joined = unsafe { String::from_utf8_unchecked(joined) };
+        OsString::from(joined)
+    }
+}
+
+#[cfg(feature = "bstr")]
+impl<'a, I> QuoteIterExt<bstr::BString> for I
+where
+    I: IntoIterator,
+    I::Item: Into<Quotable<'a>>,
+{
+    fn quoted_join<Q: Quoter>(self, _q: Q) -> bstr::BString {
+        bstr::BString::from(Q::join(self))
+    }
+}
+
+impl<'a, I> QuoteIterExt<String> for I
+where
+    I: IntoIterator,
+    I::Item: Into<Quotable<'a>>,
+{
+    fn quoted_join<Q: Quoter>(self, _q: Q) -> String {
+        let joined = Q::join(self);
+        // SAFETY: `joined` is valid UTF-8 because it was
+        // generated by a `quote`/`join` implementation from this crate –
+        // enforced because `Quoter` is sealed.
+        unsafe { String::from_utf8_unchecked(joined) }
+    }
 }
 
 // ----------------------------------------------------------------------------
 
 pub(crate) mod quoter {
+    use crate::sink::Sink;
+
     pub trait QuoterSealed {
         /// Quote/escape a string of bytes into a new [`Vec<u8>`].
         fn quote<'a, S: ?Sized + Into<super::Quotable<'a>>>(s: S) -> Vec<u8>;
 
-        /// Quote/escape a string of bytes into an existing [`Vec<u8>`].
-        fn quote_into<'a, S: ?Sized + Into<super::Quotable<'a>>>(s: S, sout: &mut Vec<u8>);
+        /// Quote/escape a string of bytes into an existing [`Sink`], e.g. a
+        /// [`Vec<u8>`] or a [`fmt::Formatter`](core::fmt::Formatter).
+        fn quote_into<'a, S: ?Sized + Into<super::Quotable<'a>>, O: Sink + ?Sized>(
+            s: S,
+            sout: &mut O,
+        );
     }
 }
 
@@ -123,7 +418,82 @@ pub(crate) mod quoter {
 /// This is because the [`QuoteExt`] implementation for [`String`] must be sure
 /// that quoted bytes are valid UTF-8, and that is only possible if the
 /// implementation is known and tested in advance.
-pub trait Quoter: quoter::QuoterSealed {}
+pub trait Quoter: quoter::QuoterSealed {
+    /// Quote/escape a string of bytes into a new [`Vec<u8>`], refusing
+    /// inputs that contain a byte – like NUL – that cannot be quoted
+    /// portably for every shell.
+    ///
+    /// The infallible [`quote`](quoter::QuoterSealed::quote) always succeeds
+    /// and keeps today's behavior (bash's `$'...'` escaping handles any
+    /// byte). `try_quote` rejects NUL, and individual quoters may reject
+    /// more (e.g. [`Cmd`] also rejects a raw newline). Other control bytes,
+    /// such as the ESC that starts a terminal escape sequence, are not
+    /// rejected; they are quoted the same way `quote` quotes them.
+    fn try_quote<'a, S: ?Sized + Into<Quotable<'a>>>(s: S) -> Result<Vec<u8>, QuoteError> {
+        let s = s.into();
+        error::check_quotable(&s.bytes)?;
+        Ok(Self::quote(s))
+    }
+
+    /// Quote/escape a string of bytes into an existing [`Vec<u8>`]. See
+    /// [`try_quote`](Quoter::try_quote).
+    fn try_quote_into<'a, S: ?Sized + Into<Quotable<'a>>>(
+        s: S,
+        sout: &mut Vec<u8>,
+    ) -> Result<(), QuoteError> {
+        let s = s.into();
+        error::check_quotable(&s.bytes)?;
+        Self::quote_into(s, sout);
+        Ok(())
+    }
+
+    /// Quote each argument in `args` and join the results with a single
+    /// space, producing one runnable command line.
+    ///
+    /// An empty argument is still emitted as an explicit empty token rather
+    /// than disappearing, so that, for [`Sh`], `split(Q::join(args))`
+    /// round-trips back to `args`. This relies on each `Quoter`'s own
+    /// `quote_into` already rendering an empty input as *some* non-empty,
+    /// unambiguous token (e.g. `''` for [`Sh`]/[`Bash`]/[`PowerShell`], `^"^"`
+    /// for [`Cmd`]), which all quoters in this crate do. [`split`] doesn't
+    /// understand bash's `$'...'` escaping, so [`Bash`]'s own round-trip only
+    /// holds for arguments that [`Sh`] would have quoted identically; see
+    /// [`split`]'s documentation.
+    fn join<'a, I>(args: I) -> Vec<u8>
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        let mut sout = Vec::new();
+        Self::join_into(args, &mut sout);
+        sout
+    }
+
+    /// Quote each argument in `args` and join the results with a single
+    /// space into an existing [`Vec<u8>`]. See [`join`](Quoter::join).
+    fn join_into<'a, I>(args: I, sout: &mut Vec<u8>)
+    where
+        I: IntoIterator,
+        I::Item: Into<Quotable<'a>>,
+    {
+        for (i, arg) in args.into_iter().enumerate() {
+            if i > 0 {
+                sout.push(b' ');
+            }
+            Self::quote_into(arg, sout);
+        }
+    }
+
+    /// Wrap `s` in a lazy [`Display`](std::fmt::Display)/[`Debug`](std::fmt::Debug)
+    /// adapter that quotes it only when formatted, e.g.
+    /// `write!(out, "run {}", Bash.display(path))`.
+    fn display<'a, S: ?Sized + Into<Quotable<'a>>>(self, s: S) -> QuotedDisplay<'a, Self>
+    where
+        Self: Sized,
+    {
+        QuotedDisplay::new(s)
+    }
+}
 
 // ----------------------------------------------------------------------------
 
@@ -133,25 +503,35 @@ pub trait Quoter: quoter::QuoterSealed {}
 /// constraint. Why not accept [`AsRef<[u8]>`] instead? The ergonomics of that
 /// approach were not so good. For example, quoting [`OsString`]/[`OsStr`] and
 /// [`PathBuf`]/[`Path`] didn't work in a natural way.
+///
+/// The bytes are usually borrowed from the source, but are sometimes owned –
+/// for example on Windows, where [`OsStr`] is UTF-16 and must be re-encoded
+/// before it can be quoted as bytes.
 pub struct Quotable<'a> {
-    pub(crate) bytes: &'a [u8],
+    pub(crate) bytes: Cow<'a, [u8]>,
 }
 
 impl<'a> From<&'a [u8]> for Quotable<'a> {
     fn from(source: &'a [u8]) -> Quotable<'a> {
-        Quotable { bytes: source }
+        Quotable {
+            bytes: Cow::Borrowed(source),
+        }
     }
 }
 
 impl<'a, const N: usize> From<&'a [u8; N]> for Quotable<'a> {
     fn from(source: &'a [u8; N]) -> Quotable<'a> {
-        Quotable { bytes: &source[..] }
+        Quotable {
+            bytes: Cow::Borrowed(&source[..]),
+        }
     }
 }
 
 impl<'a> From<&'a Vec<u8>> for Quotable<'a> {
     fn from(source: &'a Vec<u8>) -> Quotable<'a> {
-        Quotable { bytes: source }
+        Quotable {
+            bytes: Cow::Borrowed(source),
+        }
     }
 }
 
@@ -167,7 +547,7 @@ impl<'a> From<&'a String> for Quotable<'a> {
     }
 }
 
-#[cfg(unix)]
+#[cfg(all(feature = "std", unix))]
 impl<'a> From<&'a OsStr> for Quotable<'a> {
     fn from(source: &'a OsStr) -> Quotable<'a> {
         use std::os::unix::ffi::OsStrExt;
@@ -175,7 +555,7 @@ impl<'a> From<&'a OsStr> for Quotable<'a> {
     }
 }
 
-#[cfg(unix)]
+#[cfg(all(feature = "std", unix))]
 impl<'a> From<&'a OsString> for Quotable<'a> {
     fn from(source: &'a OsString) -> Quotable<'a> {
         use std::os::unix::ffi::OsStrExt;
@@ -199,16 +579,116 @@ impl<'a> From<&'a bstr::BString> for Quotable<'a> {
     }
 }
 
-#[cfg(unix)]
+#[cfg(all(feature = "std", unix))]
+impl<'a> From<&'a Path> for Quotable<'a> {
+    fn from(source: &'a Path) -> Quotable<'a> {
+        source.as_os_str().into()
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+impl<'a> From<&'a PathBuf> for Quotable<'a> {
+    fn from(source: &'a PathBuf) -> Quotable<'a> {
+        source.as_os_str().into()
+    }
+}
+
+// Windows' `OsStr` is UTF-16 under the hood, not bytes, so there is no
+// borrowed byte slice to point at; it has to be re-encoded into an owned
+// buffer first. This uses a lossless WTF-8-style re-encoding rather than
+// `String::from_utf16_lossy` so that an unpaired surrogate (which a real
+// `OsStr`/path can legitimately contain) is preserved instead of being
+// silently replaced with U+FFFD.
+#[cfg(all(feature = "std", windows))]
+impl<'a> From<&'a OsStr> for Quotable<'a> {
+    fn from(source: &'a OsStr) -> Quotable<'a> {
+        use std::os::windows::ffi::OsStrExt;
+        let utf16: Vec<u16> = source.encode_wide().collect();
+        let bytes = wtf8::from_utf16(&utf16);
+        Quotable {
+            bytes: Cow::Owned(bytes),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", windows))]
+impl<'a> From<&'a OsString> for Quotable<'a> {
+    fn from(source: &'a OsString) -> Quotable<'a> {
+        source.as_os_str().into()
+    }
+}
+
+#[cfg(all(feature = "std", windows))]
 impl<'a> From<&'a Path> for Quotable<'a> {
     fn from(source: &'a Path) -> Quotable<'a> {
         source.as_os_str().into()
     }
 }
 
-#[cfg(unix)]
+#[cfg(all(feature = "std", windows))]
 impl<'a> From<&'a PathBuf> for Quotable<'a> {
     fn from(source: &'a PathBuf) -> Quotable<'a> {
         source.as_os_str().into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn join_keeps_an_empty_argument_as_a_token_for_cmd() {
+        // Regression test: `join`/`join_into` used to hardcode the POSIX
+        // empty-token `''` for every quoter, which `cmd.exe` has no special
+        // meaning for. It must instead delegate to each quoter's own
+        // `quote_into`, which already renders an empty argument as *some*
+        // unambiguous token in its own scheme.
+        assert_eq!(Cmd::join(["a", "", "b"]), b"^\"a^\" ^\"^\" ^\"b^\"");
+    }
+
+    #[test]
+    fn join_keeps_an_empty_argument_as_a_token_for_powershell() {
+        assert_eq!(PowerShell::join(["a", "", "b"]), b"'a' '' 'b'");
+    }
+
+    #[test]
+    fn join_into_appends_to_existing_buffer() {
+        let mut sout = vec![b'$', b' '];
+        PowerShell::join_into(["a", "b"], &mut sout);
+        assert_eq!(sout, b"$ 'a' 'b'");
+    }
+
+    #[test]
+    fn try_quote_rejects_nul_for_every_quoter() {
+        assert_eq!(
+            PowerShell::try_quote(&b"a\0b"[..]),
+            Err(QuoteError { byte: 0, offset: 1 })
+        );
+        assert_eq!(
+            Cmd::try_quote(&b"a\0b"[..]),
+            Err(QuoteError { byte: 0, offset: 1 })
+        );
+    }
+
+    #[test]
+    fn try_quote_into_leaves_the_buffer_untouched_on_error() {
+        let mut sout = Vec::new();
+        assert!(PowerShell::try_quote_into(&b"a\0b"[..], &mut sout).is_err());
+        assert!(sout.is_empty());
+    }
+
+    #[test]
+    fn try_push_quoted_rejects_nul() {
+        let mut sout = Vec::new();
+        assert!(sout.try_push_quoted(PowerShell, &b"a\0b"[..]).is_err());
+        assert!(sout.is_empty());
+    }
+
+    #[test]
+    fn try_quoted_rejects_nul() {
+        let result: Result<Vec<u8>, QuoteError> = b"a\0b"[..].try_quoted(PowerShell);
+        assert_eq!(result, Err(QuoteError { byte: 0, offset: 1 }));
+    }
+}
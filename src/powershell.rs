@@ -0,0 +1,92 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::quoter::QuoterSealed;
+use crate::sink::Sink;
+#[cfg(test)]
+use crate::QuoteError;
+use crate::{Quotable, Quoter};
+
+/// Quote/escape bytes for use as a single PowerShell command-line argument.
+///
+/// The argument is wrapped in `'...'`, and any embedded `'` is escaped by
+/// doubling it, per PowerShell's single-quoted string literal rules.
+///
+/// PowerShell, like `cmd.exe`, reads a command line as UTF-16 text, not
+/// bytes, so there is no faithful way to represent a byte sequence that
+/// isn't valid UTF-8. Any invalid sequence is replaced with U+FFFD (as
+/// [`String::from_utf8_lossy`] does) rather than being copied through
+/// verbatim, since every caller of this crate's `unsafe` string conversions
+/// relies on every [`Quoter`] always producing valid UTF-8.
+pub struct PowerShell;
+
+impl QuoterSealed for PowerShell {
+    fn quote<'a, S: ?Sized + Into<Quotable<'a>>>(s: S) -> Vec<u8> {
+        let mut sout = Vec::new();
+        Self::quote_into(s, &mut sout);
+        sout
+    }
+
+    fn quote_into<'a, S: ?Sized + Into<Quotable<'a>>, O: Sink + ?Sized>(s: S, sout: &mut O) {
+        let input = s.into();
+        let text = String::from_utf8_lossy(&input.bytes);
+        let bytes = text.as_bytes();
+
+        sout.reserve(bytes.len() + 2);
+        sout.push(b'\'');
+        for &b in bytes {
+            if b == b'\'' {
+                sout.push(b'\'');
+            }
+            sout.push(b);
+        }
+        sout.push(b'\'');
+    }
+}
+
+impl Quoter for PowerShell {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_plain_text_in_single_quotes() {
+        assert_eq!(PowerShell::quote("abc"), b"'abc'");
+    }
+
+    #[test]
+    fn doubles_embedded_single_quotes() {
+        assert_eq!(PowerShell::quote("it's"), b"'it''s'");
+    }
+
+    #[test]
+    fn empty_input_is_an_explicit_empty_token() {
+        assert_eq!(PowerShell::quote(""), b"''");
+    }
+
+    #[test]
+    fn try_quote_rejects_nul() {
+        assert_eq!(
+            PowerShell::try_quote(&b"a\0b"[..]),
+            Err(QuoteError { byte: 0, offset: 1 })
+        );
+    }
+
+    #[test]
+    fn try_quote_accepts_ordinary_text() {
+        assert_eq!(PowerShell::try_quote("abc").unwrap(), b"'abc'");
+    }
+
+    #[test]
+    fn replaces_invalid_utf8_instead_of_copying_it_through() {
+        let quoted = PowerShell::quote(&b"\xff"[..]);
+        assert!(core::str::from_utf8(&quoted).is_ok());
+        assert_eq!(quoted, b"'\xEF\xBF\xBD'");
+    }
+
+    #[test]
+    fn passes_valid_multi_byte_utf8_through_unchanged() {
+        assert_eq!(PowerShell::quote("café"), "'café'".as_bytes());
+    }
+}
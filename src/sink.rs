@@ -0,0 +1,56 @@
+use alloc::vec::Vec;
+
+/// A byte-at-a-time output target for [`QuoterSealed::quote_into`](crate::quoter::QuoterSealed::quote_into).
+///
+/// This exists so that quoting can write straight into something other than
+/// a [`Vec<u8>`] – e.g. a [`fmt::Formatter`](core::fmt::Formatter) – without
+/// first collecting into an intermediate buffer.
+pub(crate) trait Sink {
+    fn push(&mut self, byte: u8);
+
+    fn push_slice(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.push(b);
+        }
+    }
+
+    /// Hint that `additional` more bytes are about to be pushed. Sinks that
+    /// can't benefit from reserving ahead of time (e.g. a `Formatter`) can
+    /// ignore this.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+}
+
+impl Sink for Vec<u8> {
+    fn push(&mut self, byte: u8) {
+        Vec::push(self, byte);
+    }
+
+    fn push_slice(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+}
+
+#[cfg(feature = "bstr")]
+impl Sink for bstr::BString {
+    fn push(&mut self, byte: u8) {
+        // Fully-qualified so this doesn't just call itself: `self` only
+        // resolves to this impl's own `push`, not `Vec<u8>::push`, unless we
+        // name the target type explicitly (autoderef isn't tried first the
+        // way it is for method calls).
+        Vec::push(self, byte);
+    }
+
+    fn push_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+}
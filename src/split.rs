@@ -0,0 +1,221 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+
+use crate::Quotable;
+
+/// An error produced by [`split`] when the input is not well-formed as a
+/// POSIX `sh` word list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// A `'` or `"` was opened but never closed.
+    UnterminatedQuote,
+    /// The input ended with a `\` that had nothing left to escape.
+    TrailingBackslash,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnterminatedQuote => write!(f, "unterminated quote"),
+            ParseError::TrailingBackslash => write!(f, "trailing unescaped backslash"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// The four states of the POSIX `sh` word-splitting state machine.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Between words; whitespace is still being skipped.
+    Delimiting,
+    /// Inside a word, outside of any quotes.
+    Unquoted,
+    /// Inside a `'...'` span.
+    SingleQuoted,
+    /// Inside a `"..."` span.
+    DoubleQuoted,
+}
+
+/// Split a shell command line into its argument words, following POSIX `sh`
+/// word-splitting and quoting rules.
+///
+/// This is the inverse of quoting with [`Sh`](crate::Sh): quoting a list of
+/// arguments and then splitting the result back apart yields the original
+/// arguments.
+///
+/// [`Bash`](crate::Bash) quotes most arguments identically to [`Sh`], but
+/// falls back to bash-only `$'...'` ANSI-C quoting for bytes `Sh` can't
+/// represent at all (e.g. a literal `\x07`). This function only implements
+/// POSIX `sh` quoting, so it does not parse `$'...'`; splitting a
+/// `Bash`-quoted argument that needed it will not recover the original
+/// bytes. Round-tripping through [`Bash`] specifically is only guaranteed for
+/// arguments that don't need `$'...'` quoting in the first place.
+///
+/// Unquoted runs of space, tab, or newline separate words. A `\` escapes the
+/// following byte literally. Inside `'...'` every byte is copied verbatim
+/// until the closing quote; inside `"..."` every byte is copied verbatim
+/// except that `\` only escapes `$`, `` ` ``, `"`, `\`, and newline. A word is
+/// emitted as soon as it is closed off by whitespace or by the end of input,
+/// even if it is made up entirely of (now-empty) quoted spans, so `a''b`
+/// splits into the single word `ab`.
+pub fn split<'a>(input: impl Into<Quotable<'a>>) -> Result<Vec<Vec<u8>>, ParseError> {
+    let input = input.into();
+    let bytes = input.bytes;
+
+    let mut words = Vec::new();
+    let mut word = Vec::new();
+    let mut in_word = false;
+    let mut state = State::Delimiting;
+    let mut iter = bytes.iter().copied();
+
+    while let Some(b) = iter.next() {
+        match state {
+            State::Delimiting | State::Unquoted => match b {
+                b' ' | b'\t' | b'\n' => {
+                    if in_word {
+                        words.push(mem::take(&mut word));
+                        in_word = false;
+                    }
+                    state = State::Delimiting;
+                }
+                b'\\' => {
+                    word.push(iter.next().ok_or(ParseError::TrailingBackslash)?);
+                    in_word = true;
+                    state = State::Unquoted;
+                }
+                b'\'' => {
+                    in_word = true;
+                    state = State::SingleQuoted;
+                }
+                b'"' => {
+                    in_word = true;
+                    state = State::DoubleQuoted;
+                }
+                _ => {
+                    word.push(b);
+                    in_word = true;
+                    state = State::Unquoted;
+                }
+            },
+            State::SingleQuoted => match b {
+                b'\'' => state = State::Unquoted,
+                _ => word.push(b),
+            },
+            State::DoubleQuoted => match b {
+                b'"' => state = State::Unquoted,
+                b'\\' => match iter.next().ok_or(ParseError::UnterminatedQuote)? {
+                    escaped @ (b'$' | b'`' | b'"' | b'\\') => word.push(escaped),
+                    b'\n' => {}
+                    other => {
+                        word.push(b'\\');
+                        word.push(other);
+                    }
+                },
+                _ => word.push(b),
+            },
+        }
+    }
+
+    if state == State::SingleQuoted || state == State::DoubleQuoted {
+        return Err(ParseError::UnterminatedQuote);
+    }
+    if in_word {
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(s: &str) -> Vec<Vec<u8>> {
+        split(s).unwrap()
+    }
+
+    #[test]
+    fn splits_plain_whitespace() {
+        assert_eq!(
+            words("one two  three"),
+            vec![b"one".to_vec(), b"two".to_vec(), b"three".to_vec()]
+        );
+    }
+
+    #[test]
+    fn adjacent_quotes_merge_into_one_word() {
+        assert_eq!(words("a''b"), vec![b"ab".to_vec()]);
+    }
+
+    #[test]
+    fn single_quotes_do_not_escape() {
+        assert_eq!(words(r"'a\b'"), vec![br"a\b".to_vec()]);
+    }
+
+    #[test]
+    fn double_quotes_only_escape_a_few_bytes() {
+        assert_eq!(words(r#""a\$b\!c""#), vec![br"a$b\!c".to_vec()]);
+    }
+
+    #[test]
+    fn backslash_escapes_outside_quotes() {
+        assert_eq!(words(r"a\ b"), vec![b"a b".to_vec()]);
+    }
+
+    #[test]
+    fn unterminated_single_quote_errors() {
+        assert_eq!(split("'a"), Err(ParseError::UnterminatedQuote));
+    }
+
+    #[test]
+    fn unterminated_double_quote_errors() {
+        assert_eq!(split("\"a"), Err(ParseError::UnterminatedQuote));
+    }
+
+    #[test]
+    fn trailing_backslash_errors() {
+        assert_eq!(split("a\\"), Err(ParseError::TrailingBackslash));
+    }
+
+    #[test]
+    fn empty_input_yields_no_words() {
+        assert_eq!(split("").unwrap(), Vec::<Vec<u8>>::new());
+    }
+
+    /// POSIX `sh` quoting wraps a word in `'...'`, doubling up into
+    /// `'"'"'` around each embedded `'` (`Sh::quote`'s own algorithm isn't
+    /// available in this module, so the expected quoting is spelled out by
+    /// hand here instead of calling it).
+    fn sh_quote(word: &str) -> alloc::string::String {
+        let mut out = alloc::string::String::from("'");
+        for ch in word.chars() {
+            if ch == '\'' {
+                out.push_str("'\"'\"'");
+            } else {
+                out.push(ch);
+            }
+        }
+        out.push('\'');
+        out
+    }
+
+    #[test]
+    fn sh_quoting_round_trips_through_split() {
+        let args = ["plain", "has space", "it's", "", "a\"b"];
+        let line = args
+            .iter()
+            .map(|a| sh_quote(a))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let words = split(line.as_str()).unwrap();
+        assert_eq!(
+            words,
+            args.iter()
+                .map(|a| a.as_bytes().to_vec())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+/// Re-encode UTF-16 code units (as produced by `OsStr::encode_wide` on
+/// Windows) into WTF-8 bytes, losslessly.
+///
+/// This differs from [`String::from_utf16_lossy`] in that an unpaired
+/// surrogate – which a real `OsStr`/path can legitimately contain – is kept
+/// as its own 3-byte sequence instead of being replaced with U+FFFD. The
+/// result is not necessarily valid UTF-8, but it is a faithful, reversible
+/// re-encoding of the original bytes, which is what quoting needs.
+pub(crate) fn from_utf16(units: &[u16]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(units.len());
+    let mut iter = units.iter().copied().peekable();
+    while let Some(unit) = iter.next() {
+        match unit {
+            0xD800..=0xDBFF => match iter.peek() {
+                Some(&low) if (0xDC00..=0xDFFF).contains(&low) => {
+                    iter.next();
+                    let c =
+                        0x10000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+                    push_scalar(&mut bytes, c);
+                }
+                _ => push_surrogate(&mut bytes, unit),
+            },
+            0xDC00..=0xDFFF => push_surrogate(&mut bytes, unit),
+            _ => push_scalar(&mut bytes, u32::from(unit)),
+        }
+    }
+    bytes
+}
+
+fn push_scalar(bytes: &mut Vec<u8>, c: u32) {
+    let ch = char::from_u32(c).expect("surrogate pairs and non-surrogate units are valid chars");
+    let mut buf = [0u8; 4];
+    bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+}
+
+/// Encode a lone surrogate as the 3-byte sequence its code point would take
+/// in UTF-8, even though U+D800..=U+DFFF is not itself a valid `char`.
+fn push_surrogate(bytes: &mut Vec<u8>, unit: u16) {
+    let c = u32::from(unit);
+    bytes.push(0xE0 | (c >> 12) as u8);
+    bytes.push(0x80 | ((c >> 6) & 0x3F) as u8);
+    bytes.push(0x80 | (c & 0x3F) as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_plain_text() {
+        let units: Vec<u16> = "hello".encode_utf16().collect();
+        assert_eq!(from_utf16(&units), b"hello");
+    }
+
+    #[test]
+    fn round_trips_astral_characters() {
+        let units: Vec<u16> = "a\u{1F600}b".encode_utf16().collect();
+        assert_eq!(from_utf16(&units), "a\u{1F600}b".as_bytes());
+    }
+
+    #[test]
+    fn preserves_lone_surrogates_instead_of_replacing_them() {
+        let units = [0x0061u16, 0xD800, 0x0062];
+        let bytes = from_utf16(&units);
+        // Not U+FFFD (which `from_utf16_lossy` would have produced); the
+        // lone surrogate keeps its own 3-byte slot.
+        assert_eq!(bytes, [b'a', 0xED, 0xA0, 0x80, b'b']);
+    }
+}